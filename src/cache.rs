@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    etag: String,
+    body: String,
+}
+
+/// An on-disk cache of API response bodies keyed by endpoint URL, alongside
+/// the `ETag` they were served with, so a later run can send `If-None-Match`
+/// and reuse the cached body instead of re-downloading an unchanged resource.
+#[derive(Clone)]
+pub struct Cache {
+    path: PathBuf,
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl Cache {
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let entries = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+        })
+    }
+
+    pub fn etag(&self, url: &str) -> Option<String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(url)
+            .map(|entry| entry.etag.clone())
+    }
+
+    pub fn body(&self, url: &str) -> Option<String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(url)
+            .map(|entry| entry.body.clone())
+    }
+
+    pub fn put(&self, url: String, etag: String, body: String) {
+        self.entries.lock().unwrap().insert(url, Entry { etag, body });
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let entries = self.entries.lock().unwrap();
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&*entries)?)?;
+        Ok(())
+    }
+}
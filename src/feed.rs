@@ -0,0 +1,69 @@
+use atom_syndication::{Content, Entry, Feed, Link, Person};
+
+use crate::model::IssueWithComments;
+
+/// Builds an Atom feed with one `<entry>` per issue, newest `updated_at`
+/// first, so the result can be piped straight to a feed reader.
+pub fn build(title: &str, link: &str, mut data: Vec<IssueWithComments>) -> anyhow::Result<Feed> {
+    data.sort_by(|a, b| b.issue.updated_at.cmp(&a.issue.updated_at));
+
+    // `data` is now sorted newest-`updated_at`-first, so the feed's own
+    // `<updated>` is just the first entry's; fall back to now for an empty
+    // export so the feed still carries a meaningful timestamp.
+    let updated = match data.first() {
+        Some(d) => d.issue.updated_at.parse::<atom_syndication::FixedDateTime>()?,
+        None => atom_syndication::FixedDateTime::parse_from_rfc3339(&chrono::Utc::now().to_rfc3339())?,
+    };
+
+    let entries = data
+        .into_iter()
+        .map(|IssueWithComments { issue, comments }| {
+            let mut content = issue.body;
+            for comment in comments {
+                content.push_str("\n\n---\n\n");
+                content.push_str(&comment.body);
+            }
+
+            let mut entry = Entry::default();
+            entry.set_title(issue.title);
+            entry.set_id(issue.html_url.clone());
+            entry.set_updated(issue.updated_at.parse::<atom_syndication::FixedDateTime>()?);
+            entry.set_authors(vec![Person {
+                name: issue.user.login,
+                ..Default::default()
+            }]);
+            entry.set_links(vec![Link {
+                href: issue.html_url,
+                ..Default::default()
+            }]);
+            entry.set_content(Content {
+                // `content` is raw, unrendered issue/comment markdown, not
+                // HTML — label it "text" so feed readers don't try to render
+                // markdown syntax as markup.
+                value: Some(content),
+                content_type: Some("text".to_string()),
+                ..Default::default()
+            });
+            Ok(entry)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut feed = Feed::default();
+    feed.set_title(title);
+    // The feed id must be a permanent, non-empty URI per RFC 4287 — the
+    // repo's own link is the natural stable identifier.
+    feed.set_id(link);
+    feed.set_updated(updated);
+    feed.set_links(vec![Link {
+        href: link.to_string(),
+        ..Default::default()
+    }]);
+    feed.set_entries(entries);
+    Ok(feed)
+}
+
+pub fn write(path: impl AsRef<std::path::Path>, feed: &Feed) -> anyhow::Result<()> {
+    let f = std::fs::File::create(path)?;
+    feed.write_to(f)?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use anyhow::{anyhow, bail};
+use headers::{
+    authorization::Bearer, Authorization, ContentType, ETag, HeaderMapExt, IfNoneMatch, UserAgent,
+};
+use hyper::{
+    client::{Client, HttpConnector},
+    Body, Request, StatusCode,
+};
+use hyper_tls::HttpsConnector;
+use tracing::{debug, info};
+
+use crate::cache;
+use crate::model::{Comment, Issue};
+use crate::{Query, State};
+
+use super::{backoff, is_retryable, next_page_url, retry_wait, Forge, MAX_ATTEMPTS};
+
+#[derive(Clone)]
+pub struct GithubForge {
+    client: Client<HttpsConnector<HttpConnector>>,
+    user_agent: UserAgent,
+    auth: Authorization<Bearer>,
+    host: String,
+    cache: Option<cache::Cache>,
+}
+
+impl GithubForge {
+    pub fn new(
+        auth: Authorization<Bearer>,
+        host: impl Into<String>,
+        cache: Option<cache::Cache>,
+    ) -> anyhow::Result<Self> {
+        let user_agent = UserAgent::from_static(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ));
+        let https = HttpsConnector::new();
+        let client = hyper::Client::builder().build(https);
+        Ok(Self {
+            client,
+            user_agent,
+            auth,
+            host: host.into(),
+            cache,
+        })
+    }
+
+    /// GitHub's SaaS API is served from `api.github.com` directly, but
+    /// GitHub Enterprise Server instances serve their REST API under an
+    /// `/api/v3` prefix on the instance's own host.
+    fn api_endpoint(&self) -> String {
+        if self.host == "api.github.com" {
+            format!("https://{}", self.host)
+        } else {
+            format!("https://{}/api/v3", self.host)
+        }
+    }
+
+    async fn get<T>(&self, endpoint: &str) -> anyhow::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let (body, _) = self.get_page(endpoint).await?;
+        Ok(body)
+    }
+
+    /// Fetches a single page of `endpoint` and returns the deserialized body
+    /// together with the `rel="next"` URL from the `Link` header, if any.
+    async fn get_page<T>(&self, endpoint: &str) -> anyhow::Result<(T, Option<String>)>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let cached_etag = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.etag(endpoint))
+            .and_then(|etag| etag.parse::<ETag>().ok());
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut req = Request::get(endpoint);
+            if let Some(headers) = req.headers_mut() {
+                headers.typed_insert(self.user_agent.clone());
+                headers.typed_insert(self.auth.clone());
+                headers.typed_insert(ContentType::json());
+                if let Some(etag) = cached_etag.clone() {
+                    headers.typed_insert(IfNoneMatch::from(etag));
+                }
+            }
+            let req = req.body(Body::empty())?;
+
+            debug!(?req, "request");
+            let resp = self.client.request(req).await?;
+            let status = resp.status();
+            let next = next_page_url(resp.headers());
+            let etag = resp.headers().typed_get::<ETag>();
+
+            if status == StatusCode::NOT_MODIFIED {
+                let cache = self.cache.as_ref().ok_or_else(|| {
+                    anyhow!("received 304 Not Modified without a cache configured")
+                })?;
+                let body = cache.body(endpoint).ok_or_else(|| {
+                    anyhow!("received 304 Not Modified for an uncached endpoint")
+                })?;
+                return Ok((serde_json::from_str(&body)?, next));
+            }
+
+            if is_retryable(status, resp.headers()) && attempt < MAX_ATTEMPTS {
+                let wait = retry_wait(resp.headers()).unwrap_or_else(|| backoff(attempt));
+                info!(
+                    "{endpoint} returned {status}, retrying in {wait:?} (attempt {attempt}/{MAX_ATTEMPTS})",
+                );
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+
+            let body_bytes = hyper::body::to_bytes(resp.into_body()).await?;
+
+            if status.is_success() {
+                if let (Some(cache), Some(etag)) = (&self.cache, etag) {
+                    cache.put(
+                        endpoint.to_string(),
+                        etag.to_string(),
+                        String::from_utf8_lossy(&body_bytes).into_owned(),
+                    );
+                }
+                return Ok((serde_json::from_slice(&body_bytes)?, next));
+            } else {
+                bail!("request failed: {}", String::from_utf8_lossy(&body_bytes));
+            }
+        }
+        unreachable!("loop always returns or bails before exhausting MAX_ATTEMPTS")
+    }
+
+    /// Follows `rel="next"` links until exhausted, accumulating every page
+    /// into a single `Vec`.
+    async fn get_all<T>(&self, endpoint: &str) -> anyhow::Result<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut items = Vec::new();
+        let mut next = Some(endpoint.to_string());
+        while let Some(url) = next {
+            let (page, next_url): (Vec<T>, _) = self.get_page(&url).await?;
+            items.extend(page);
+            next = next_url;
+        }
+        Ok(items)
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GithubForge {
+    async fn issue(&self, query: &Query) -> anyhow::Result<Issue> {
+        let Query {
+            username,
+            repo,
+            issue,
+            ..
+        } = query;
+        let issue = issue.expect("logic error: querying issue without issue number");
+        let endpoint = self.api_endpoint();
+        self.get(&format!("{endpoint}/repos/{username}/{repo}/issues/{issue}"))
+            .await
+    }
+
+    async fn issues(&self, query: &Query, state: State) -> anyhow::Result<Vec<Issue>> {
+        let Query { username, repo, .. } = query;
+        let endpoint = self.api_endpoint();
+        self.get_all(&format!(
+            "{endpoint}/repos/{username}/{repo}/issues?state={state}&per_page=100",
+        ))
+        .await
+    }
+
+    async fn comments(&self, issue: &Issue) -> anyhow::Result<Vec<Comment>> {
+        self.get_all(&format!("{}?per_page=100", issue.comments_url))
+            .await
+    }
+}
@@ -0,0 +1,320 @@
+//! A GitLab `Forge` backend.
+
+use anyhow::{anyhow, bail};
+use headers::{ETag, HeaderMapExt, IfNoneMatch, UserAgent};
+use hyper::{
+    client::{Client, HttpConnector},
+    Body, Request, StatusCode,
+};
+use hyper_tls::HttpsConnector;
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use crate::cache;
+use crate::model::{Comment, Issue, Label, User};
+use crate::{Query, State};
+
+use super::{backoff, is_retryable, next_page_url, retry_wait, Forge, MAX_ATTEMPTS};
+
+#[derive(Clone)]
+pub struct GitlabForge {
+    client: Client<HttpsConnector<HttpConnector>>,
+    user_agent: UserAgent,
+    token: String,
+    host: String,
+    cache: Option<cache::Cache>,
+}
+
+impl GitlabForge {
+    pub fn new(
+        token: impl Into<String>,
+        host: impl Into<String>,
+        cache: Option<cache::Cache>,
+    ) -> anyhow::Result<Self> {
+        let user_agent = UserAgent::from_static(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ));
+        let https = HttpsConnector::new();
+        let client = hyper::Client::builder().build(https);
+        Ok(Self {
+            client,
+            user_agent,
+            token: token.into(),
+            host: host.into(),
+            cache,
+        })
+    }
+
+    /// Fetches a single page of `endpoint` and returns the deserialized body
+    /// together with the `rel="next"` URL from the `Link` header, if any.
+    /// Mirrors `GithubForge::get_page`'s ETag-based conditional request and
+    /// rate-limit retry/backoff.
+    async fn get_page<T>(&self, endpoint: &str) -> anyhow::Result<(T, Option<String>)>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let cached_etag = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.etag(endpoint))
+            .and_then(|etag| etag.parse::<ETag>().ok());
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut req = Request::get(endpoint);
+            if let Some(headers) = req.headers_mut() {
+                headers.typed_insert(self.user_agent.clone());
+                headers.insert("PRIVATE-TOKEN", self.token.parse()?);
+                if let Some(etag) = cached_etag.clone() {
+                    headers.typed_insert(IfNoneMatch::from(etag));
+                }
+            }
+            let req = req.body(Body::empty())?;
+
+            debug!(?req, "request");
+            let resp = self.client.request(req).await?;
+            let status = resp.status();
+            let next = next_page_url(resp.headers());
+            let etag = resp.headers().typed_get::<ETag>();
+
+            if status == StatusCode::NOT_MODIFIED {
+                let cache = self.cache.as_ref().ok_or_else(|| {
+                    anyhow!("received 304 Not Modified without a cache configured")
+                })?;
+                let body = cache.body(endpoint).ok_or_else(|| {
+                    anyhow!("received 304 Not Modified for an uncached endpoint")
+                })?;
+                return Ok((serde_json::from_str(&body)?, next));
+            }
+
+            if is_retryable(status, resp.headers()) && attempt < MAX_ATTEMPTS {
+                let wait = retry_wait(resp.headers()).unwrap_or_else(|| backoff(attempt));
+                info!(
+                    "{endpoint} returned {status}, retrying in {wait:?} (attempt {attempt}/{MAX_ATTEMPTS})",
+                );
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+
+            let body_bytes = hyper::body::to_bytes(resp.into_body()).await?;
+
+            if status.is_success() {
+                if let (Some(cache), Some(etag)) = (&self.cache, etag) {
+                    cache.put(
+                        endpoint.to_string(),
+                        etag.to_string(),
+                        String::from_utf8_lossy(&body_bytes).into_owned(),
+                    );
+                }
+                return Ok((serde_json::from_slice(&body_bytes)?, next));
+            } else {
+                bail!("request failed: {}", String::from_utf8_lossy(&body_bytes));
+            }
+        }
+        unreachable!("loop always returns or bails before exhausting MAX_ATTEMPTS")
+    }
+
+    async fn get<T>(&self, endpoint: &str) -> anyhow::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let (body, _) = self.get_page(endpoint).await?;
+        Ok(body)
+    }
+
+    /// Follows `rel="next"` links until exhausted, accumulating every page
+    /// into a single `Vec`.
+    async fn get_all<T>(&self, endpoint: &str) -> anyhow::Result<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut items = Vec::new();
+        let mut next = Some(endpoint.to_string());
+        while let Some(url) = next {
+            let (page, next_url): (Vec<T>, _) = self.get_page(&url).await?;
+            items.extend(page);
+            next = next_url;
+        }
+        Ok(items)
+    }
+
+    /// GitLab addresses a project by numeric id or by its URL-encoded
+    /// `namespace/name` path.
+    fn project(&self, query: &Query) -> String {
+        format!("{}%2F{}", query.username, query.repo.replace('/', "%2F"))
+    }
+
+    fn notes_url(&self, project_id: u64, issue_iid: u64) -> String {
+        format!(
+            "https://{}/api/v4/projects/{project_id}/issues/{issue_iid}/notes?per_page=100",
+            self.host,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl Forge for GitlabForge {
+    async fn issue(&self, query: &Query) -> anyhow::Result<Issue> {
+        let iid = query
+            .issue
+            .expect("logic error: querying issue without issue number");
+        let project = self.project(query);
+        let issue: GitlabIssue = self
+            .get(&format!(
+                "https://{}/api/v4/projects/{project}/issues/{iid}",
+                self.host,
+            ))
+            .await?;
+        let notes_url = self.notes_url(issue.project_id, issue.iid);
+        Ok(issue.into_model(notes_url))
+    }
+
+    async fn issues(&self, query: &Query, state: State) -> anyhow::Result<Vec<Issue>> {
+        let project = self.project(query);
+        let state = match state {
+            State::Open => "&state=opened",
+            State::Closed => "&state=closed",
+            State::All => "",
+        };
+        let issues: Vec<GitlabIssue> = self
+            .get_all(&format!(
+                "https://{}/api/v4/projects/{project}/issues?per_page=100{state}",
+                self.host,
+            ))
+            .await?;
+        Ok(issues
+            .into_iter()
+            .map(|issue| {
+                let notes_url = self.notes_url(issue.project_id, issue.iid);
+                issue.into_model(notes_url)
+            })
+            .collect())
+    }
+
+    async fn comments(&self, issue: &Issue) -> anyhow::Result<Vec<Comment>> {
+        let notes: Vec<GitlabNote> = self.get_all(&issue.comments_url).await?;
+        Ok(notes
+            .into_iter()
+            .filter(|note| !note.system)
+            .map(Into::into)
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabUser {
+    id: u64,
+    username: String,
+    #[allow(dead_code)]
+    name: String,
+    avatar_url: Option<String>,
+    web_url: String,
+}
+
+impl From<GitlabUser> for User {
+    fn from(u: GitlabUser) -> Self {
+        User {
+            login: u.username,
+            id: u.id,
+            avatar_url: u.avatar_url.unwrap_or_default(),
+            gravatar_id: String::new(),
+            url: u.web_url.clone(),
+            html_url: u.web_url,
+            followers_url: String::new(),
+            following_url: String::new(),
+            gists_url: String::new(),
+            starred_url: String::new(),
+            subscriptions_url: String::new(),
+            organizations_url: String::new(),
+            repos_url: String::new(),
+            events_url: String::new(),
+            received_events_url: String::new(),
+            site_admin: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabIssue {
+    id: u64,
+    iid: u64,
+    project_id: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    web_url: String,
+    author: GitlabUser,
+    assignee: Option<GitlabUser>,
+    labels: Vec<String>,
+    user_notes_count: u64,
+    created_at: String,
+    updated_at: String,
+    closed_at: Option<String>,
+}
+
+impl GitlabIssue {
+    /// Maps GitLab's issue shape onto the shared `model::Issue`, normalizing
+    /// `state` to GitHub's `open`/`closed` values.
+    fn into_model(self, comments_url: String) -> Issue {
+        Issue {
+            id: self.id,
+            url: self.web_url.clone(),
+            labels_url: String::new(),
+            comments_url,
+            events_url: String::new(),
+            html_url: self.web_url,
+            number: self.iid,
+            state: if self.state == "opened" {
+                "open".to_string()
+            } else {
+                self.state
+            },
+            title: self.title,
+            body: self.description.unwrap_or_default(),
+            user: self.author.into(),
+            labels: self
+                .labels
+                .into_iter()
+                .map(|name| Label {
+                    url: String::new(),
+                    name,
+                    color: String::new(),
+                })
+                .collect(),
+            assignee: self.assignee.map(Into::into),
+            locked: false,
+            comments: self.user_notes_count,
+            closed_at: self.closed_at,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabNote {
+    id: u64,
+    body: String,
+    author: GitlabUser,
+    created_at: String,
+    updated_at: String,
+    /// True for GitLab's auto-generated activity notes (label changes,
+    /// assignment, state changes, …), which have no GitHub equivalent and
+    /// shouldn't be exported as comments.
+    system: bool,
+}
+
+impl From<GitlabNote> for Comment {
+    fn from(note: GitlabNote) -> Self {
+        Comment {
+            id: note.id,
+            url: String::new(),
+            html_url: String::new(),
+            body: note.body,
+            user: note.author.into(),
+            created_at: note.created_at,
+            updated_at: note.updated_at,
+        }
+    }
+}
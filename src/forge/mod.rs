@@ -0,0 +1,99 @@
+mod github;
+mod gitlab;
+
+pub use github::GithubForge;
+pub use gitlab::GitlabForge;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyper::header::HeaderMap;
+use hyper::StatusCode;
+
+use crate::model::{Comment, Issue};
+use crate::{Query, State};
+
+pub(crate) const MAX_ATTEMPTS: u32 = 5;
+pub(crate) const MAX_BACKOFF: Duration = Duration::from_secs(30);
+pub(crate) const MAX_RATE_LIMIT_SLEEP: Duration = Duration::from_secs(15 * 60);
+
+/// A hosted issue tracker: today GitHub and GitLab, mapped onto the same
+/// `model::Issue`/`model::Comment` shape so the rest of the tool doesn't
+/// need to know which one it's talking to.
+#[async_trait::async_trait]
+pub trait Forge: Send + Sync {
+    async fn issue(&self, query: &Query) -> anyhow::Result<Issue>;
+    async fn issues(&self, query: &Query, state: State) -> anyhow::Result<Vec<Issue>>;
+    async fn comments(&self, issue: &Issue) -> anyhow::Result<Vec<Comment>>;
+}
+
+/// Parses the `rel="next"` URL out of an RFC 8288 pagination `Link` header,
+/// shared by both the GitHub and GitLab clients (GitHub docs:
+/// https://docs.github.com/en/rest/guides/using-pagination-in-the-rest-api,
+/// GitLab uses the identical header shape).
+pub(crate) fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(hyper::header::LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let mut segments = part.split(';');
+        let url = segments.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        segments
+            .any(|segment| segment.trim() == r#"rel="next""#)
+            .then(|| url.to_string())
+    })
+}
+
+/// Whether a status is worth retrying: a secondary rate limit (429, or a 403
+/// that actually carries an exhausted rate-limit header) or a transient
+/// server-side failure. Shared by both the GitHub and GitLab clients.
+///
+/// A bare 403 is also how both forges report permanent permission failures
+/// (bad token scope, SSO enforcement, no access to a private repo), which no
+/// amount of retrying fixes — those can still carry `remaining > 0`, so we
+/// only treat a 403 as transient when the rate-limit budget is actually
+/// exhausted.
+pub(crate) fn is_retryable(status: StatusCode, headers: &HeaderMap) -> bool {
+    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        return true;
+    }
+    status == StatusCode::FORBIDDEN && remaining_rate_limit(headers) == Some(0)
+}
+
+/// Reads GitHub's `x-ratelimit-remaining` or GitLab's `ratelimit-remaining`
+/// header, if present.
+fn remaining_rate_limit(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get("x-ratelimit-remaining")
+        .or_else(|| headers.get("ratelimit-remaining"))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// How long to wait before retrying, based on `Retry-After` or, failing
+/// that, the primary rate limit's `x-ratelimit-remaining`/`x-ratelimit-reset`
+/// (GitHub) or `ratelimit-remaining`/`ratelimit-reset` (GitLab) headers.
+pub(crate) fn retry_wait(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(secs) = headers
+        .get(hyper::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let remaining = remaining_rate_limit(headers)?;
+    if remaining > 0 {
+        return None;
+    }
+
+    let reset: u64 = headers
+        .get("x-ratelimit-reset")
+        .or_else(|| headers.get("ratelimit-reset"))
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now)).min(MAX_RATE_LIMIT_SLEEP))
+}
+
+/// Exponential backoff for the given (1-indexed) attempt number.
+pub(crate) fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(500 * 2u64.pow(attempt - 1)).min(MAX_BACKOFF)
+}
@@ -0,0 +1,106 @@
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderError};
+use serde::Serialize;
+
+use crate::model::Issue;
+
+/// `{{date created_at "%Y-%m-%d"}}` — reformats an RFC3339 timestamp (as
+/// returned by GitHub/GitLab for `created_at`/`updated_at`) with a
+/// `chrono`-style format string, defaulting to `%Y-%m-%d`.
+pub fn date_format(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let value = h
+        .param(0)
+        .and_then(|v| v.value().as_str())
+        .ok_or_else(|| RenderError::new("date helper requires a timestamp parameter"))?;
+    let format = h
+        .param(1)
+        .and_then(|v| v.value().as_str())
+        .unwrap_or("%Y-%m-%d");
+
+    let parsed = chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|e| RenderError::new(format!("invalid RFC3339 timestamp {value:?}: {e}")))?;
+    out.write(&parsed.format(format).to_string())?;
+    Ok(())
+}
+
+/// `{{join issue.labels ", "}}` — joins an array into a string, pulling the
+/// `name` field out of label-shaped objects and falling back to the value
+/// itself otherwise. Each item is written as a quoted JSON string (which is
+/// also a valid YAML flow scalar), so names containing the separator, quotes,
+/// or other YAML-significant characters can't corrupt the caller's output.
+pub fn join(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let items = h
+        .param(0)
+        .and_then(|v| v.value().as_array())
+        .ok_or_else(|| RenderError::new("join helper requires an array parameter"))?;
+    let separator = h.param(1).and_then(|v| v.value().as_str()).unwrap_or(", ");
+
+    let joined = items
+        .iter()
+        .map(|item| match item.get("name").and_then(|n| n.as_str()) {
+            Some(name) => name,
+            None => item.as_str().unwrap_or_default(),
+        })
+        .map(|name| serde_json::to_string(name).unwrap_or_else(|_| "\"\"".to_string()))
+        .collect::<Vec<_>>()
+        .join(separator);
+    out.write(&joined)?;
+    Ok(())
+}
+
+/// The subset of `Issue` that goes into a rendered file's YAML front matter.
+#[derive(Serialize)]
+struct FrontMatter<'a> {
+    title: &'a str,
+    state: &'a str,
+    labels: Vec<&'a str>,
+    assignee: Option<&'a str>,
+    created: &'a str,
+    updated: &'a str,
+}
+
+/// `{{front_matter issue}}` — renders an issue's YAML front matter via
+/// `serde_yaml`, so titles, labels, and logins with quotes, colons, or
+/// commas can't corrupt the surrounding YAML (unlike hand-rolled
+/// `"{{issue.title}}"`/`[{{join ...}}]` syntax, which only gets Handlebars'
+/// HTML escaping, not YAML escaping).
+pub fn front_matter(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let issue = h
+        .param(0)
+        .and_then(|v| v.value().clone().as_object().cloned())
+        .ok_or_else(|| RenderError::new("front_matter helper requires an issue parameter"))?;
+    let issue: Issue = serde_json::from_value(serde_json::Value::Object(issue))
+        .map_err(|e| RenderError::new(format!("front_matter helper: invalid issue: {e}")))?;
+
+    let front = FrontMatter {
+        title: &issue.title,
+        state: &issue.state,
+        labels: issue.labels.iter().map(|label| label.name.as_str()).collect(),
+        assignee: issue.assignee.as_ref().map(|u| u.login.as_str()),
+        created: &issue.created_at,
+        updated: &issue.updated_at,
+    };
+    let yaml = serde_yaml::to_string(&front)
+        .map_err(|e| RenderError::new(format!("front_matter helper: {e}")))?;
+    out.write("---\n")?;
+    out.write(&yaml)?;
+    out.write("---")?;
+    Ok(())
+}
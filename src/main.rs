@@ -1,133 +1,25 @@
-use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
-use std::{fmt, io};
+use std::sync::Arc;
 
 use anyhow::{anyhow, bail};
 use argh::FromArgs;
 use futures::StreamExt;
 use handlebars::Handlebars;
-use headers::{authorization::Bearer, Authorization, ContentType, HeaderMapExt, UserAgent};
-use hyper::{
-    client::{Client, HttpConnector},
-    Body, Request,
-};
-use hyper_tls::HttpsConnector;
+use headers::Authorization;
 use serde::Deserialize;
-use tracing::{debug, info};
 
+mod cache;
+mod feed;
+mod forge;
+mod helpers;
 mod model;
+mod store;
 mod template;
 
-#[derive(Clone)]
-struct Github {
-    client: Client<HttpsConnector<HttpConnector>>,
-    user_agent: UserAgent,
-    auth: Authorization<Bearer>,
-}
-
-const API_ENDPOINT: &str = "https://api.github.com";
-
-impl Github {
-    pub fn new(auth: Authorization<Bearer>) -> anyhow::Result<Self> {
-        let user_agent = UserAgent::from_static(concat!(
-            env!("CARGO_PKG_NAME"),
-            "/",
-            env!("CARGO_PKG_VERSION")
-        ));
-        let https = HttpsConnector::new();
-        let client = hyper::Client::builder().build(https);
-        Ok(Self {
-            client,
-            user_agent,
-            auth,
-        })
-    }
-
-    async fn get<T>(&self, endpoint: &str) -> anyhow::Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let mut req = Request::get(endpoint);
-        if let Some(headers) = req.headers_mut() {
-            headers.typed_insert(self.user_agent.clone());
-            headers.typed_insert(self.auth.clone());
-            headers.typed_insert(ContentType::json());
-        }
-        let req = req.body(Body::empty())?;
-
-        debug!(?req, "request");
-        let resp = self.client.request(req).await?;
-        let status = resp.status();
-        let body_bytes = hyper::body::to_bytes(resp.into_body()).await?;
-
-        if status.is_success() {
-            Ok(serde_json::from_slice(&body_bytes)?)
-        } else {
-            bail!("request failed: {}", String::from_utf8_lossy(&body_bytes));
-        }
-    }
-
-    async fn issue(
-        &self,
-        Query {
-            username,
-            repo,
-            issue,
-        }: &Query,
-    ) -> anyhow::Result<model::Issue> {
-        let issue = issue.expect("logic error: querying issue without issue number");
-        self.get(&format!(
-            "{API_ENDPOINT}/repos/{username}/{repo}/issues/{issue}",
-        ))
-        .await
-    }
-
-    async fn issues(
-        &self,
-        Query { username, repo, .. }: &Query,
-        state: State,
-    ) -> anyhow::Result<Vec<model::Issue>> {
-        self.get(&format!(
-            "{API_ENDPOINT}/repos/{username}/{repo}/issues?state={state}",
-        ))
-        .await
-    }
-}
-
-fn mkdir(path: impl AsRef<Path>) -> io::Result<()> {
-    if let Err(e) = std::fs::create_dir(path) {
-        match e.kind() {
-            std::io::ErrorKind::AlreadyExists => (),
-            _ => {
-                return Err(e);
-            }
-        }
-    };
-    Ok(())
-}
-
-fn issue_to_filename(path: impl AsRef<Path>, issue: &model::Issue) -> String {
-    format!(
-        "{}/{:03}-{}.md",
-        path.as_ref().display(),
-        issue.number,
-        slug::slugify(&issue.title),
-    )
-}
-
-fn serialize(
-    path: impl AsRef<Path>,
-    hb: &mut Handlebars,
-    data: &model::IssueWithComments,
-) -> anyhow::Result<()> {
-    let md = hb.render("issue", &data)?;
-    let filename = issue_to_filename(path, &data.issue);
-    let mut f = std::fs::File::create(&filename)?;
-    info!("Writing name {}", filename);
-    f.write_all(md.as_bytes())?;
-    Ok(())
-}
+use forge::Forge;
+use store::Store;
 
 #[derive(Debug, Deserialize)]
 enum State {
@@ -159,9 +51,38 @@ impl fmt::Display for State {
     }
 }
 
-/// Export issues from GitHub into markdown files.
+/// Which `Store` backend to persist exported issues with.
+#[derive(Debug)]
+enum StoreKind {
+    Markdown,
+    Sqlite,
+}
+
+impl FromStr for StoreKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "markdown" => Self::Markdown,
+            "sqlite" => Self::Sqlite,
+            _ => bail!("unknown store: {s}"),
+        })
+    }
+}
+
+/// Which forge a `Query` targets, picked from a `gitlab:` prefix on the
+/// positional query argument (GitHub is the default).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+/// Export issues from GitHub or GitLab into markdown files or a SQLite
+/// database.
 ///
-/// Requires environment variable GITHUB_TOKEN (in environment or .env file)
+/// Requires environment variable GITHUB_TOKEN for GitHub repos, or
+/// GITLAB_TOKEN for `gitlab:`-prefixed ones (in environment or .env file)
 #[derive(Debug, FromArgs)]
 struct Args {
     /// output directory [default: ./md]
@@ -170,7 +91,26 @@ struct Args {
     /// fetch issues that are open, closed, or both [default: open]
     #[argh(option, short = 's', default = "State::Open")]
     state: State,
-    /// query of the form: username/repo[#issue_number]
+    /// directory to cache API responses in, enabling conditional requests on
+    /// subsequent runs (no caching by default)
+    #[argh(option)]
+    cache_dir: Option<PathBuf>,
+    /// write an Atom feed of the exported issues to this path
+    #[argh(option)]
+    feed: Option<PathBuf>,
+    /// storage backend: markdown or sqlite [default: markdown]
+    #[argh(option, default = "StoreKind::Markdown")]
+    store: StoreKind,
+    /// path to a custom Handlebars template for rendering issues [default:
+    /// built-in template]
+    #[argh(option)]
+    template: Option<PathBuf>,
+    /// API host to talk to [default: api.github.com, or gitlab.com for
+    /// `gitlab:`-prefixed queries], for GitHub Enterprise or self-hosted
+    /// GitLab instances
+    #[argh(option)]
+    host: Option<String>,
+    /// query of the form: [gitlab:]username/repo[#issue_number]
     #[argh(positional)]
     query: Query,
 }
@@ -180,12 +120,18 @@ struct Query {
     username: String,
     repo: String,
     issue: Option<usize>,
+    forge: ForgeKind,
 }
 
 impl FromStr for Query {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (forge, s) = match s.strip_prefix("gitlab:") {
+            Some(rest) => (ForgeKind::GitLab, rest),
+            None => (ForgeKind::GitHub, s),
+        };
+
         let (username, repo) = s
             .split_once('/')
             .ok_or_else(|| anyhow!("invalid query: {s}"))?;
@@ -203,6 +149,7 @@ impl FromStr for Query {
             username: username.to_string(),
             repo: repo.to_string(),
             issue,
+            forge,
         })
     }
 }
@@ -212,35 +159,90 @@ const MAX_PARALLEL_FETCHES: usize = 8;
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = init();
-    let token = dotenv::var("GITHUB_TOKEN")
-        .map_err(|_| anyhow!("missing obligatory environment variable GITHUB_TOKEN"))?;
+    let cache = args
+        .cache_dir
+        .as_ref()
+        .map(|dir| cache::Cache::load(dir.join("cache.json")))
+        .transpose()?;
 
-    let auth = Authorization::bearer(&token)?;
-    let github = Github::new(auth)?;
+    let forge: Arc<dyn Forge> = match args.query.forge {
+        ForgeKind::GitHub => {
+            let token = dotenv::var("GITHUB_TOKEN")
+                .map_err(|_| anyhow!("missing obligatory environment variable GITHUB_TOKEN"))?;
+            let auth = Authorization::bearer(&token)?;
+            let host = args.host.clone().unwrap_or_else(|| "api.github.com".to_string());
+            Arc::new(forge::GithubForge::new(auth, host, cache.clone())?)
+        }
+        ForgeKind::GitLab => {
+            let token = dotenv::var("GITLAB_TOKEN")
+                .map_err(|_| anyhow!("missing obligatory environment variable GITLAB_TOKEN"))?;
+            let host = args.host.clone().unwrap_or_else(|| "gitlab.com".to_string());
+            Arc::new(forge::GitlabForge::new(token, host, cache.clone())?)
+        }
+    };
 
     let mut reg = Handlebars::new();
-    reg.register_template_string("issue", template::TEMPLATE)?;
+    reg.register_helper("date", Box::new(helpers::date_format));
+    reg.register_helper("join", Box::new(helpers::join));
+    reg.register_helper("front_matter", Box::new(helpers::front_matter));
+    match &args.template {
+        Some(path) => reg.register_template_string("issue", std::fs::read_to_string(path)?)?,
+        None => reg.register_template_string("issue", template::TEMPLATE)?,
+    }
+
+    let mut store: Box<dyn Store> = match args.store {
+        StoreKind::Markdown => Box::new(store::MarkdownStore::new(&args.path, reg)?),
+        StoreKind::Sqlite => {
+            std::fs::create_dir_all(&args.path)?;
+            Box::new(store::SqliteStore::open(args.path.join("issues.db"))?)
+        }
+    };
 
     let issues: Vec<model::Issue> = if args.query.issue.is_some() {
-        let issue = github.issue(&args.query).await?;
+        let issue = forge.issue(&args.query).await?;
         vec![issue]
     } else {
-        github.issues(&args.query, args.state).await?
+        forge.issues(&args.query, args.state).await?
     };
 
     let mut issues = futures::stream::iter(issues.into_iter().map(|issue| {
-        let github = github.clone();
+        let forge = forge.clone();
         async move {
-            let comments: Vec<model::Comment> = github.get(&issue.comments_url).await?;
+            let comments = forge.comments(&issue).await?;
             Ok::<_, anyhow::Error>(model::IssueWithComments { issue, comments })
         }
     }))
     .buffer_unordered(MAX_PARALLEL_FETCHES);
 
-    mkdir(&args.path)?;
-
+    let mut exported = Vec::new();
     while let Some(data) = issues.next().await {
-        serialize(&args.path, &mut reg, &data?)?;
+        let data = data?;
+        store.save(&data)?;
+        exported.push(data);
+    }
+
+    if let Some(feed_path) = &args.feed {
+        let repo = format!("{}/{}", args.query.username, args.query.repo);
+        let link = match args.query.forge {
+            ForgeKind::GitHub => {
+                let host = args.host.as_deref().unwrap_or("api.github.com");
+                if host == "api.github.com" {
+                    format!("https://github.com/{repo}")
+                } else {
+                    format!("https://{host}/{repo}")
+                }
+            }
+            ForgeKind::GitLab => {
+                let host = args.host.as_deref().unwrap_or("gitlab.com");
+                format!("https://{host}/{repo}")
+            }
+        };
+        let feed = feed::build(&repo, &link, exported)?;
+        feed::write(feed_path, &feed)?;
+    }
+
+    if let Some(cache) = cache {
+        cache.save()?;
     }
 
     Ok(())
@@ -0,0 +1,55 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use handlebars::Handlebars;
+
+use crate::model::{Issue, IssueWithComments};
+
+use super::Store;
+
+/// Writes each issue to its own `NNN-slug.md` file, rendered through a
+/// Handlebars template.
+pub struct MarkdownStore<'a> {
+    path: PathBuf,
+    hb: Handlebars<'a>,
+}
+
+impl<'a> MarkdownStore<'a> {
+    pub fn new(path: impl Into<PathBuf>, hb: Handlebars<'a>) -> anyhow::Result<Self> {
+        let path = path.into();
+        mkdir(&path)?;
+        Ok(Self { path, hb })
+    }
+}
+
+impl<'a> Store for MarkdownStore<'a> {
+    fn save(&mut self, data: &IssueWithComments) -> anyhow::Result<()> {
+        let md = self.hb.render("issue", data)?;
+        let filename = issue_to_filename(&self.path, &data.issue);
+        let mut f = std::fs::File::create(&filename)?;
+        tracing::info!("Writing name {}", filename);
+        f.write_all(md.as_bytes())?;
+        Ok(())
+    }
+}
+
+fn mkdir(path: impl AsRef<Path>) -> std::io::Result<()> {
+    if let Err(e) = std::fs::create_dir(path) {
+        match e.kind() {
+            std::io::ErrorKind::AlreadyExists => (),
+            _ => {
+                return Err(e);
+            }
+        }
+    };
+    Ok(())
+}
+
+fn issue_to_filename(path: impl AsRef<Path>, issue: &Issue) -> String {
+    format!(
+        "{}/{:03}-{}.md",
+        path.as_ref().display(),
+        issue.number,
+        slug::slugify(&issue.title),
+    )
+}
@@ -0,0 +1,13 @@
+mod markdown;
+mod sqlite;
+
+pub use markdown::MarkdownStore;
+pub use sqlite::SqliteStore;
+
+use crate::model::IssueWithComments;
+
+/// Persists exported issues somewhere durable: markdown files by default, or
+/// a queryable SQLite database via `--store sqlite`.
+pub trait Store {
+    fn save(&mut self, data: &IssueWithComments) -> anyhow::Result<()>;
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::model::IssueWithComments;
+
+use super::Store;
+
+/// Upserts issues and comments into a SQLite database, keyed by issue
+/// number, so exports can be queried and diffed locally without re-parsing
+/// markdown.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS issues (
+                number      INTEGER PRIMARY KEY,
+                state       TEXT NOT NULL,
+                title       TEXT NOT NULL,
+                body        TEXT NOT NULL,
+                user_login  TEXT NOT NULL,
+                html_url    TEXT NOT NULL,
+                created_at  TEXT NOT NULL,
+                updated_at  TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS comments (
+                id            INTEGER PRIMARY KEY,
+                issue_number  INTEGER NOT NULL REFERENCES issues(number),
+                body          TEXT NOT NULL,
+                user_login    TEXT NOT NULL,
+                html_url      TEXT NOT NULL,
+                created_at    TEXT NOT NULL,
+                updated_at    TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+}
+
+impl Store for SqliteStore {
+    fn save(&mut self, data: &IssueWithComments) -> anyhow::Result<()> {
+        let issue = &data.issue;
+        self.conn.execute(
+            "INSERT INTO issues
+                (number, state, title, body, user_login, html_url, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(number) DO UPDATE SET
+                state = excluded.state,
+                title = excluded.title,
+                body = excluded.body,
+                user_login = excluded.user_login,
+                html_url = excluded.html_url,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at",
+            params![
+                issue.number,
+                issue.state,
+                issue.title,
+                issue.body,
+                issue.user.login,
+                issue.html_url,
+                issue.created_at,
+                issue.updated_at,
+            ],
+        )?;
+
+        for comment in &data.comments {
+            self.conn.execute(
+                "INSERT INTO comments
+                    (id, issue_number, body, user_login, html_url, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(id) DO UPDATE SET
+                    body = excluded.body,
+                    user_login = excluded.user_login,
+                    html_url = excluded.html_url,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at",
+                params![
+                    comment.id,
+                    issue.number,
+                    comment.body,
+                    comment.user.login,
+                    comment.html_url,
+                    comment.created_at,
+                    comment.updated_at,
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-pub const TEMPLATE: &'static str = r#"# [{{issue.title}}]({{issue.html_url}})
+pub const TEMPLATE: &'static str = r#"{{front_matter issue}}
+
+# [{{issue.title}}]({{issue.html_url}})
 
 > state: **{{issue.state}}** opened by: **{{issue.user.login}}** on: **{{issue.created_at}}**
 